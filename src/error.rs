@@ -7,6 +7,9 @@ pub enum Error {
 
     #[error("GraphQL schema is invalid")]
     InvalidSchema,
+
+    #[error("{0}")]
+    InvalidSchemaSdl(#[from] Box<pest::error::Error<crate::sdl::Rule>>),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
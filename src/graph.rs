@@ -0,0 +1,265 @@
+//! A precomputed reachability graph over a schema's types, so that running many searches
+//! against the same schema doesn't repeatedly rebuild field maps and clone `String`s on every
+//! BFS step. See `TypeGraph::build` and `search`.
+
+use std::collections::{HashMap, VecDeque};
+use revql::schema::{Field, Type};
+
+pub type NodeId = usize;
+
+/// The pagination convention recognized on a collapsed Relay connection field, based on which
+/// argument pair it declares.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pagination {
+    /// `first`/`after` - the Relay cursor-connection spec.
+    Cursor,
+    /// `first`/`offset` - the offset-based convention used by e.g. pg_graphql.
+    Offset,
+}
+
+impl Pagination {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Pagination::Cursor => "cursor",
+            Pagination::Offset => "offset",
+        }
+    }
+}
+
+/// One outgoing hop from a node: a field access reaching `target`, or (when `fragment` is true)
+/// narrowing an interface/union into one of its `possibleTypes`. `field_name` holds the field
+/// name for a regular hop, or the concrete type name for a fragment hop. `collapsed` is true when
+/// this edge stands in for a Relay `field -> edges -> node` connection chain, regardless of
+/// whether `pagination` managed to recognize the field's args as one of the known conventions.
+#[derive(Clone, Debug)]
+pub struct Edge {
+    pub field_name: String,
+    pub target: NodeId,
+    pub fragment: bool,
+    pub collapsed: bool,
+    pub pagination: Option<Pagination>,
+}
+
+/// A hop resolved during BFS: the node it departs from and the index of the `Edge` taken out of
+/// it within that node's adjacency list.
+pub type Hop = (NodeId, usize);
+
+/// The type graph itself: each type in the schema gets a `NodeId`, and `adjacency` holds its
+/// outgoing field/fragment edges (only object/interface/union targets are linked). Built once per schema and reused across every
+/// `run_search` call.
+pub struct TypeGraph {
+    pub names: Vec<String>,
+    name_to_id: HashMap<String, NodeId>,
+    adjacency: Vec<Vec<Edge>>,
+}
+
+impl TypeGraph {
+    /// Builds the graph from a schema's type map. Each type is assigned a `NodeId` once, and its
+    /// edges - regular field accesses, interface/union `possibleTypes` narrowing, and (unless
+    /// `show_relay`) collapsed Relay connection chains - are resolved up front instead of being
+    /// recomputed on every search.
+    pub fn build(type_map: &HashMap<String, &Type>, show_relay: bool) -> Self {
+        let names: Vec<String> = type_map.keys().cloned().collect();
+        let name_to_id: HashMap<String, NodeId> = names.iter().cloned().enumerate().map(|(id, name)| (name, id)).collect();
+        let mut adjacency = vec![Vec::new(); names.len()];
+
+        for (id, name) in names.iter().enumerate() {
+            let t = type_map[name];
+
+            if let Some(possible_types) = &t.possible_types {
+                for possible_type in possible_types {
+                    if let Some(possible_name) = &possible_type.name {
+                        if let Some(&possible_id) = name_to_id.get(possible_name) {
+                            adjacency[id].push(Edge { field_name: possible_name.clone(), target: possible_id, fragment: true, collapsed: false, pagination: None });
+                        }
+                    }
+                }
+            }
+
+            let Some(fields) = &t.fields else { continue };
+            for field in fields {
+                if !show_relay {
+                    if let Some(target_id) = collapsed_connection_target(field, type_map, &name_to_id) {
+                        adjacency[id].push(Edge { field_name: field.name.clone(), target: target_id, fragment: false, collapsed: true, pagination: pagination_kind(field) });
+                        continue;
+                    }
+                }
+
+                let type_ref = field.field_type.get_deepest();
+                if !type_ref.is_object() && !type_ref.is_abstract() { continue; }
+                let Some(target_name) = &type_ref.name else { continue };
+                if let Some(&target_id) = name_to_id.get(target_name) {
+                    adjacency[id].push(Edge { field_name: field.name.clone(), target: target_id, fragment: false, collapsed: false, pagination: None });
+                }
+            }
+        }
+
+        Self { names, name_to_id, adjacency }
+    }
+
+    #[inline]
+    pub fn id_of(&self, name: &str) -> Option<NodeId> {
+        self.name_to_id.get(name).copied()
+    }
+
+    #[inline]
+    pub fn edges(&self, node: NodeId) -> &[Edge] {
+        &self.adjacency[node]
+    }
+}
+
+/// Detects a Relay cursor-connection field (`field: SomethingConnection` with an `edges { node }`
+/// shape) and returns the real node type it ultimately reaches, so `build` can collapse the
+/// three-hop `field -> edges -> node` chain into one edge instead of leaving the Connection/Edge
+/// plumbing as traversable nodes in their own right. Whether the field's args additionally match
+/// a recognized pagination convention is a separate question, handled by `pagination_kind`.
+fn collapsed_connection_target(
+    field: &Field,
+    type_map: &HashMap<String, &Type>,
+    name_to_id: &HashMap<String, NodeId>,
+) -> Option<NodeId> {
+    let connection_name = field.field_type.get_deepest().name?;
+    let connection_type = *type_map.get(&connection_name)?;
+    if !connection_type.is_relay() || !connection_name.ends_with("Connection") {
+        return None;
+    }
+
+    let edges_field = connection_type.get_field("edges", false)?;
+    let edge_name = edges_field.field_type.get_deepest().name?;
+    let edge_type = *type_map.get(&edge_name)?;
+    if !edge_type.is_relay() || !edge_name.ends_with("Edge") {
+        return None;
+    }
+
+    let node_field = edge_type.get_field("node", false)?;
+    let node_name = node_field.field_type.get_deepest().name?;
+    name_to_id.get(&node_name).copied()
+}
+
+/// Recognizes the Relay cursor-connection (`first`/`after`) and offset-based (`first`/`offset`)
+/// pagination argument pairs on a connection field, mirroring the conventions used by
+/// async-graphql and pg_graphql respectively.
+fn pagination_kind(field: &Field) -> Option<Pagination> {
+    let args = field.args.as_ref()?;
+    let has = |name: &str| args.iter().any(|arg| arg.name == name);
+    if has("first") && has("after") {
+        Some(Pagination::Cursor)
+    } else if has("first") && has("offset") {
+        Some(Pagination::Offset)
+    } else {
+        None
+    }
+}
+
+/// Breadth-first search over `graph` from `start` to `end`. Returns `Some(vec![])` when
+/// `start == end` (the field that got us here already lands on the target, no further hop
+/// needed), `Some(hops)` with the edges taken when a path exists, or `None` when unreachable.
+pub fn search(graph: &TypeGraph, start: NodeId, end: NodeId) -> Option<Vec<Hop>> {
+    if start == end {
+        return Some(Vec::new());
+    }
+
+    let mut visited = vec![false; graph.names.len()];
+    let mut pred: Vec<Option<Hop>> = vec![None; graph.names.len()];
+    let mut queue = VecDeque::from([start]);
+    visited[start] = true;
+
+    while let Some(current) = queue.pop_front() {
+        for (edge_idx, edge) in graph.edges(current).iter().enumerate() {
+            if visited[edge.target] { continue; }
+            visited[edge.target] = true;
+            pred[edge.target] = Some((current, edge_idx));
+
+            if edge.target == end {
+                let mut hops = Vec::new();
+                let mut node = end;
+                while let Some(hop) = pred[node] {
+                    hops.push(hop);
+                    node = hop.0;
+                }
+                hops.reverse();
+                return Some(hops);
+            }
+
+            queue.push_back(edge.target);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revql::schema::TypeRef;
+
+    fn object_field(name: &str, type_name: &str) -> Field {
+        Field { name: name.to_string(), field_type: TypeRef { name: Some(type_name.to_string()), kind: String::from("OBJECT"), of_type: None }, args: None }
+    }
+
+    fn object_type(name: &str, fields: Vec<Field>) -> Type {
+        Type { name: name.to_string(), kind: String::from("OBJECT"), fields: Some(fields), possible_types: None }
+    }
+
+    #[test]
+    fn search_finds_a_multi_hop_path_through_plain_object_fields() {
+        let a = object_type("A", vec![object_field("toB", "B")]);
+        let b = object_type("B", vec![object_field("toC", "C")]);
+        let c = object_type("C", vec![]);
+        let owned: HashMap<String, Type> = [a, b, c].into_iter().map(|t| (t.name.clone(), t)).collect();
+        let type_map: HashMap<String, &Type> = owned.iter().map(|(n, t)| (n.clone(), t)).collect();
+
+        let graph = TypeGraph::build(&type_map, false);
+        let start = graph.id_of("A").unwrap();
+        let end = graph.id_of("C").unwrap();
+        let hops = search(&graph, start, end).expect("C should be reachable from A via B");
+
+        let field_names: Vec<_> = hops.iter().map(|&(from, idx)| graph.edges(from)[idx].field_name.clone()).collect();
+        assert_eq!(field_names, vec!["toB", "toC"]);
+    }
+
+    #[test]
+    fn search_returns_none_when_unreachable() {
+        let a = object_type("A", vec![]);
+        let b = object_type("B", vec![]);
+        let owned: HashMap<String, Type> = [a, b].into_iter().map(|t| (t.name.clone(), t)).collect();
+        let type_map: HashMap<String, &Type> = owned.iter().map(|(n, t)| (n.clone(), t)).collect();
+
+        let graph = TypeGraph::build(&type_map, false);
+        let start = graph.id_of("A").unwrap();
+        let end = graph.id_of("B").unwrap();
+        assert!(search(&graph, start, end).is_none());
+    }
+
+    #[test]
+    fn search_traverses_into_possible_types_via_fragment_edges() {
+        let dog = object_type("Dog", Vec::new());
+        let cat = object_type("Cat", Vec::new());
+        let animal = Type {
+            name: String::from("Animal"),
+            kind: String::from("INTERFACE"),
+            fields: Some(Vec::new()),
+            possible_types: Some(vec![
+                TypeRef { name: Some(String::from("Dog")), kind: String::from("OBJECT"), of_type: None },
+                TypeRef { name: Some(String::from("Cat")), kind: String::from("OBJECT"), of_type: None },
+            ]),
+        };
+        let query = object_type("Query", vec![Field {
+            name: String::from("pet"),
+            field_type: TypeRef { name: Some(String::from("Animal")), kind: String::from("INTERFACE"), of_type: None },
+            args: None,
+        }]);
+        let owned: HashMap<String, Type> = [dog, cat, animal, query].into_iter().map(|t| (t.name.clone(), t)).collect();
+        let type_map: HashMap<String, &Type> = owned.iter().map(|(n, t)| (n.clone(), t)).collect();
+
+        let graph = TypeGraph::build(&type_map, false);
+        let start = graph.id_of("Query").unwrap();
+        let end = graph.id_of("Cat").unwrap();
+        let hops = search(&graph, start, end).expect("Cat should be reachable through the Animal interface's possibleTypes");
+
+        assert_eq!(hops.len(), 2, "Query -(pet)-> Animal -(... on Cat)-> Cat");
+        let (from, idx) = hops[1];
+        let fragment_edge = &graph.edges(from)[idx];
+        assert!(fragment_edge.fragment);
+        assert_eq!(fragment_edge.field_name, "Cat");
+    }
+}
@@ -1,13 +1,16 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+mod graph;
+
+use std::collections::{BTreeSet, HashMap};
 use std::fmt::{Display, Formatter};
 use std::fs;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use colored::{Color, ColoredString, Colorize};
-use revql::schema::{Root, Type};
+use revql::schema::{Root, Type, TypeRef};
+use graph::{Hop, TypeGraph};
 
 #[derive(Clone, Debug, Parser)]
 struct Args {
-    /// Path to JSON file containing the introspection.
+    /// Path to JSON file containing the introspection, or a `.graphql`/`.sdl` schema file.
     #[clap(required = true)]
     file: String,
 
@@ -27,21 +30,56 @@ struct Args {
     #[clap(short, long = "field")]
     field_only: bool,
 
-    /// Shows relay types.
+    /// Shows relay types, and disables collapsing Relay connection chains into a single
+    /// paginated hop.
     #[clap(long = "show-relay")]
     show_relay: bool,
+
+    /// Emits each found path as a runnable GraphQL query/mutation document instead of the
+    /// plain `A.f -> B.g -> Target` summary.
+    #[clap(long = "emit-query")]
+    emit_query: bool,
+
+    /// Parses `file` as a GraphQL SDL schema instead of introspection JSON. Inferred
+    /// automatically when `file` ends in `.graphql`/`.gql`/`.sdl`.
+    #[clap(long)]
+    sdl: bool,
+
+    /// Output format for found paths.
+    #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    /// The default `A.f -> B.g -> Target` colored summary.
+    Text,
+    /// One `{"target": ..., "path": [...]}` object per line.
+    Json,
+    /// A Graphviz digraph covering every path found, merging shared prefixes into one
+    /// reachability tree. Emitted once, after every search has run.
+    Dot,
 }
 
 #[derive(Clone, Debug)]
 struct TypeField {
     type_name: String,
     field_name: Option<String>,
+    /// True when this hop is a `... on ConcreteType` fragment narrowing an interface/union,
+    /// rather than a regular field access.
+    fragment: bool,
+    /// True when this hop stands in for a collapsed Relay `field -> edges -> node` chain.
+    collapsed: bool,
+    /// The pagination convention recognized on a collapsed field's args, if any.
+    pagination: Option<graph::Pagination>,
 }
 
 impl Display for TypeField {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        if let Some(field_name) = &self.field_name {
-            write!(f, "{}.{}", self.type_name, field_name)
+        if self.fragment {
+            write!(f, "... on {}", self.field_name.as_deref().unwrap_or(&self.type_name))
+        } else if let Some(field_name) = &self.field_name {
+            write!(f, "{}.{}{}", self.type_name, field_name, pagination_suffix(self.pagination))
         } else {
             write!(f, "{}", self.type_name)
         }
@@ -53,160 +91,597 @@ impl TypeField {
         Self {
             type_name: String::from(type_name),
             field_name,
+            fragment: false,
+            collapsed: false,
+            pagination: None,
+        }
+    }
+
+    /// Creates a hop representing an `... on ConcreteType` fragment reached from `abstract_type`
+    /// (an interface or union) via its `possibleTypes`.
+    fn new_fragment(abstract_type: &str, concrete_type: &str) -> Self {
+        Self {
+            type_name: String::from(abstract_type),
+            field_name: Some(String::from(concrete_type)),
+            fragment: true,
+            collapsed: false,
+            pagination: None,
+        }
+    }
+
+    /// Creates a hop representing a regular field access, or (when `collapsed`) one that stands
+    /// in for a Relay `field -> edges -> node` connection chain walked under the hood.
+    fn new_field(type_name: &str, field_name: String, collapsed: bool, pagination: Option<graph::Pagination>) -> Self {
+        Self {
+            type_name: String::from(type_name),
+            field_name: Some(field_name),
+            fragment: false,
+            collapsed,
+            pagination,
         }
     }
 
     fn get_colored(&self, type_color: Color, field_color: Color) -> ColoredString {
-        if let Some(field_name) = &self.field_name {
-            format!("{}.{}", self.type_name.color(type_color), field_name.color(field_color)).into()
+        if self.fragment {
+            format!("... on {}", self.field_name.as_deref().unwrap_or(&self.type_name).color(field_color)).into()
+        } else if let Some(field_name) = &self.field_name {
+            format!("{}.{}{}", self.type_name.color(type_color), field_name.color(field_color), pagination_suffix(self.pagination)).into()
         } else {
             self.type_name.color(type_color)
         }
     }
 }
 
-fn search(
-    start_type: &str,
-    end_type: &str,
-    type_map: &HashMap<String, &Type>,
-) -> Vec<TypeField> {
-    if start_type == end_type {
-        return vec![TypeField::new(start_type, None)];
-    }
-
-    let mut visited: HashSet<_> = HashSet::from_iter([String::from(start_type)]);
-    let mut queue = VecDeque::from_iter([String::from(start_type)]);
-    let mut path: HashMap<String, Option<TypeField>> = HashMap::from([(String::from(start_type), None)]);
-    while let Some(current) = queue.pop_front() {
-        if !type_map.contains_key(&current) { continue; }
-        if type_map[&current].name == end_type {
-            let mut current_path = &path[end_type];
-            let mut result = Vec::new();
-            while let Some(current_type_field) = current_path {
-                result.push(current_type_field.clone());
-                current_path = &path[&current_type_field.type_name];
-            }
-            return result.iter().rev().cloned().collect();
-        }
-
-        let field_map = &type_map[&current].get_field_map();
-        for (field_name, field) in field_map {
-            let type_ref = field.field_type.get_deepest();
-            if !type_ref.is_object() { continue; }
-            if let Some(type_name) = type_ref.name {
-                if visited.contains(&type_name) { continue; }
-                visited.insert(type_name.clone());
-                queue.push_back(type_name.clone());
-                path.insert(type_name.clone(), Some(TypeField::new(&current, Some(field_name.clone()))));
-            }
-        }
+/// Renders `" (paginated: cursor)"`/`" (paginated: offset)"` for a collapsed connection hop, or
+/// an empty string for a regular hop.
+fn pagination_suffix(pagination: Option<graph::Pagination>) -> String {
+    match pagination {
+        Some(kind) => format!(" (paginated: {})", kind.as_str()),
+        None => String::new(),
     }
-    Vec::new()
+}
+
+/// Turns the edges resolved by `graph::search` back into `TypeField` hops.
+fn reconstruct_path(graph: &TypeGraph, hops: &[Hop]) -> Vec<TypeField> {
+    hops.iter().map(|&(from, edge_idx)| {
+        let edge = &graph.edges(from)[edge_idx];
+        if edge.fragment {
+            TypeField::new_fragment(&graph.names[from], &edge.field_name)
+        } else {
+            TypeField::new_field(&graph.names[from], edge.field_name.clone(), edge.collapsed, edge.pagination)
+        }
+    }).collect()
 }
 
 fn run_search(
     start_type: &str,
     end_type: &TypeField,
-    type_map: &HashMap<String, &Type>
+    graph: &TypeGraph,
 ) -> Vec<Vec<TypeField>> {
     let mut result = Vec::new();
-    if !type_map.contains_key(start_type) {
+    let (Some(start_id), Some(end_id)) = (graph.id_of(start_type), graph.id_of(&end_type.type_name)) else {
         return result;
-    }
+    };
+
+    for edge in graph.edges(start_id) {
+        if edge.fragment { continue; }
 
-    let field_map = &type_map[start_type].get_field_map();
-    for field in field_map.values() {
-        let mut path = search(&field.get_type_name(), &end_type.type_name, type_map);
-        if !path.is_empty() {
-            path.insert(0, TypeField::new(start_type, Some(field.name.clone())));
+        if let Some(hops) = graph::search(graph, edge.target, end_id) {
+            let mut path = reconstruct_path(graph, &hops);
+            path.insert(0, TypeField::new_field(start_type, edge.field_name.clone(), edge.collapsed, edge.pagination));
             result.push(path);
         }
     }
     result
 }
 
-fn run_search_for_type(
-    end_type: &str,
+/// Options shared by a whole search run: how the search term is matched, what's shown, and how found
+/// paths get emitted.
+#[derive(Clone, Copy)]
+struct SearchOptions {
     containing: bool,
     show_relay: bool,
-    type_map: &HashMap<String, &Type>
+    emit_query: bool,
+    format: OutputFormat,
+}
+
+fn print_or_collect(
+    end_type: &TypeField,
+    path: &[TypeField],
+    root_keyword: &str,
+    opts: SearchOptions,
+    type_map: &HashMap<String, &Type>,
+    dot: &mut DotGraph,
 ) {
-    let end_types = if containing {
+    if opts.emit_query {
+        print!("{}", emit_query_doc(end_type, path, root_keyword, type_map));
+        return;
+    }
+
+    match opts.format {
+        OutputFormat::Text => print_path(end_type, path, opts.show_relay, type_map),
+        OutputFormat::Json => print_path_json(end_type, path, opts.show_relay, type_map),
+        OutputFormat::Dot => dot.add_path(end_type, path, opts.show_relay, type_map),
+    }
+}
+
+fn run_search_for_type(
+    end_type: &str,
+    opts: SearchOptions,
+    roots: &[(&str, &str)],
+    graph: &TypeGraph,
+    type_map: &HashMap<String, &Type>,
+    dot: &mut DotGraph,
+) {
+    let end_types = if opts.containing {
         type_map.iter().filter(|(name, _)| name.contains(end_type)).map(|(name, _)| TypeField::new(name, None)).collect()
     } else {
         Vec::from([TypeField::new(end_type, None)])
     };
 
     for end_type in end_types {
-        if !type_map.contains_key(&end_type.type_name) || (!show_relay && type_map[&end_type.type_name].is_relay()) {
+        if !type_map.contains_key(&end_type.type_name) || (!opts.show_relay && type_map[&end_type.type_name].is_relay()) {
             continue;
         }
 
-        for query in run_search("Query", &end_type, type_map) {
-            print_path(&end_type, &query, show_relay, type_map);
-        }
-
-        for mutation in run_search("Mutation", &end_type, type_map) {
-            print_path(&end_type, &mutation, show_relay, type_map);
+        for &(root_keyword, root) in roots {
+            for path in run_search(root, &end_type, graph) {
+                print_or_collect(&end_type, &path, root_keyword, opts, type_map, dot);
+            }
         }
     }
 }
 
 fn run_search_for_field(
     end_field: &str,
-    containing: bool,
-    show_relay: bool,
-    type_map: &HashMap<String, &Type>
+    opts: SearchOptions,
+    roots: &[(&str, &str)],
+    graph: &TypeGraph,
+    type_map: &HashMap<String, &Type>,
+    dot: &mut DotGraph,
 ) {
     for t in type_map.values() {
-        if !type_map.contains_key(&t.name) || (!show_relay && type_map[&t.name].is_relay()) {
+        if !type_map.contains_key(&t.name) || (!opts.show_relay && type_map[&t.name].is_relay()) {
             continue;
         }
 
-        let field = t.get_field(end_field, containing);
+        let field = t.get_field(end_field, opts.containing);
         if let Some(field) = field {
             let end_type = TypeField::new(&t.name, Some(field.name.clone()));
-            for query in run_search("Query", &end_type, type_map) {
-                print_path(&end_type, &query, show_relay, type_map);
-            }
-
-            for mutation in run_search("Mutation", &end_type, type_map) {
-                print_path(&end_type, &mutation, show_relay, type_map);
+            for &(root_keyword, root) in roots {
+                for path in run_search(root, &end_type, graph) {
+                    print_or_collect(&end_type, &path, root_keyword, opts, type_map, dot);
+                }
             }
         }
     }
 }
 
+/// Returns the hops of `path` that should actually be shown, skipping Relay connection/edge
+/// plumbing unless `show_relay` is set.
+fn visible_hops<'a>(path: &'a [TypeField], show_relay: bool, type_map: &HashMap<String, &Type>) -> Vec<&'a TypeField> {
+    path.iter().filter(|hop| show_relay || !type_map[&hop.type_name].is_relay()).collect()
+}
+
 fn print_path(result: &TypeField, path: &[TypeField], show_relay: bool, type_map: &HashMap<String, &Type>) {
     print!("{}: ", result.get_colored(Color::Red, Color::Red));
-    for idx in 0..path.len() {
-        if show_relay || !type_map[&path[idx].type_name].is_relay() {
-            if idx > 0 {
-                print!(" -> ")
-            }
-            print!("{}", path[idx].get_colored(Color::Green, Color::White));
+    for (idx, hop) in visible_hops(path, show_relay, type_map).into_iter().enumerate() {
+        if idx > 0 {
+            print!(" -> ")
         }
+        print!("{}", hop.get_colored(Color::Green, Color::White));
+        print!("{}", required_args_annotation(hop, type_map));
     }
     println!();
 }
 
+/// Builds the `{"target": "Type.field", "path": [{"type": ..., "field": ...}]}` JSON value for
+/// one found path, for consumption by other tooling.
+fn path_to_json(result: &TypeField, path: &[TypeField], show_relay: bool, type_map: &HashMap<String, &Type>) -> serde_json::Value {
+    let hops: Vec<serde_json::Value> = visible_hops(path, show_relay, type_map).into_iter().map(|hop| {
+        if hop.fragment {
+            serde_json::json!({ "type": hop.field_name.as_deref().unwrap_or(&hop.type_name), "field": null, "pagination": null })
+        } else {
+            serde_json::json!({ "type": hop.type_name, "field": hop.field_name, "pagination": hop.pagination.map(graph::Pagination::as_str) })
+        }
+    }).collect();
+
+    serde_json::json!({ "target": result.to_string(), "path": hops })
+}
+
+fn print_path_json(result: &TypeField, path: &[TypeField], show_relay: bool, type_map: &HashMap<String, &Type>) {
+    println!("{}", path_to_json(result, path, show_relay, type_map));
+}
+
+/// Accumulates every path found into a single Graphviz digraph, merging shared prefixes so the
+/// union of all paths to every target renders as one reachability tree instead of one disjoint
+/// graph per path.
+#[derive(Default)]
+struct DotGraph {
+    edges: BTreeSet<(String, String)>,
+}
+
+impl DotGraph {
+    fn add_path(&mut self, result: &TypeField, path: &[TypeField], show_relay: bool, type_map: &HashMap<String, &Type>) {
+        let mut prev_label: Option<String> = None;
+        for hop in visible_hops(path, show_relay, type_map) {
+            let label = hop.to_string();
+            if let Some(prev) = prev_label {
+                self.edges.insert((prev, label.clone()));
+            }
+            prev_label = Some(label);
+        }
+
+        let target_label = result.to_string();
+        if let Some(prev) = prev_label {
+            if prev != target_label {
+                self.edges.insert((prev, target_label));
+            }
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::from("digraph reachability {\n");
+        for (from, to) in &self.edges {
+            out.push_str(&format!("  \"{}\" -> \"{}\";\n", from.replace('"', "\\\""), to.replace('"', "\\\"")));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// Returns `(arg!, arg2!)` when `hop` is a field access with required (non-null, no default)
+/// arguments, or an empty string otherwise - a path is only actionable if its mandatory inputs
+/// can be satisfied.
+fn required_args_annotation(hop: &TypeField, type_map: &HashMap<String, &Type>) -> String {
+    if hop.fragment {
+        return String::new();
+    }
+
+    let Some(field_name) = &hop.field_name else {
+        return String::new();
+    };
+
+    let required = type_map.get(&hop.type_name)
+        .and_then(|t| t.get_field(field_name, false))
+        .map(|f| f.required_args())
+        .unwrap_or_default();
+
+    if required.is_empty() {
+        String::new()
+    } else {
+        format!("({})", required.iter().map(|arg| format!("{}!", arg.name)).collect::<Vec<_>>().join(", "))
+    }
+}
+
+/// Returns the selection set body for a leaf object/interface/union type: the Relay
+/// `edges { node { __typename } }` shape for connections, otherwise `__typename` plus one
+/// scalar field so the emitted document is syntactically valid.
+fn leaf_selection(type_name: &str, type_map: &HashMap<String, &Type>) -> String {
+    let Some(t) = type_map.get(type_name) else {
+        return String::from("__typename");
+    };
+
+    if t.is_relay() && t.name.ends_with("Connection") {
+        return String::from("edges { node { __typename } }");
+    }
+
+    let scalar_field = t.fields.as_ref().and_then(|fields| {
+        fields.iter().find(|f| {
+            let deepest = f.field_type.get_deepest();
+            !deepest.is_object() && !deepest.is_abstract()
+        })
+    });
+
+    match scalar_field {
+        Some(field) => format!("__typename {}", field.name),
+        None => String::from("__typename"),
+    }
+}
+
+/// Renders a `TypeRef` as GraphQL type syntax, e.g. `[ID!]!`.
+fn render_type_ref(type_ref: &TypeRef) -> String {
+    match type_ref.kind.as_str() {
+        "NON_NULL" => format!("{}!", type_ref.of_type.as_ref().map(|t| render_type_ref(t)).unwrap_or_default()),
+        "LIST" => format!("[{}]", type_ref.of_type.as_ref().map(|t| render_type_ref(t)).unwrap_or_default()),
+        _ => type_ref.name.clone().unwrap_or_default(),
+    }
+}
+
+/// Renders a field selection with placeholder variables for its required args, e.g.
+/// `field(id: $id)`, recording each new variable's declaration (`$id: ID!`) in `variables`.
+fn field_call(owner_type: &str, field_name: &str, type_map: &HashMap<String, &Type>, variables: &mut Vec<String>) -> String {
+    let required = type_map.get(owner_type)
+        .and_then(|t| t.get_field(field_name, false))
+        .map(|f| f.required_args())
+        .unwrap_or_default();
+
+    if required.is_empty() {
+        return field_name.to_string();
+    }
+
+    let args: Vec<String> = required.iter().map(|arg| {
+        // Dedup by variable name alone: two hops both needing `$id` must share one declaration
+        // even if their arg types differ, or the emitted document redeclares `$id` twice.
+        let already_declared = variables.iter().any(|declared| declared.starts_with(&format!("${}: ", arg.name)));
+        if !already_declared {
+            variables.push(format!("${}: {}", arg.name, render_type_ref(&arg.input_type)));
+        }
+        format!("{}: ${}", arg.name, arg.name)
+    }).collect();
+
+    format!("{}({})", field_name, args.join(", "))
+}
+
+/// Builds the deepest selection for `result`: either the target field itself (expanded with a
+/// `leaf_selection` if it returns an object/interface/union), or `leaf_selection` of the target
+/// type directly when `result` names a bare type.
+fn result_selection(result: &TypeField, type_map: &HashMap<String, &Type>, variables: &mut Vec<String>) -> String {
+    match &result.field_name {
+        Some(field_name) => {
+            let call = field_call(&result.type_name, field_name, type_map, variables);
+            let field_return = type_map.get(&result.type_name).and_then(|t| t.get_field(field_name, false));
+            match field_return.map(|f| f.field_type.get_deepest()) {
+                Some(deepest) if deepest.is_object() || deepest.is_abstract() => {
+                    let inner_type = deepest.name.as_deref().unwrap_or(field_name);
+                    format!("{} {{ {} }}", call, leaf_selection(inner_type, type_map))
+                }
+                _ => call,
+            }
+        }
+        None => leaf_selection(&result.type_name, type_map),
+    }
+}
+
+/// Turns a discovered path into a runnable GraphQL operation document, e.g.:
+/// ```graphql
+/// query($id: ID!) {
+///   node {
+///     target(id: $id) { __typename id }
+///   }
+/// }
+/// ```
+fn emit_query_doc(result: &TypeField, path: &[TypeField], root_keyword: &str, type_map: &HashMap<String, &Type>) -> String {
+    if path.is_empty() {
+        return String::new();
+    }
+
+    let mut variables = Vec::new();
+    let mut body = String::new();
+
+    let mut depth = 1;
+    for hop in path {
+        let indent = "  ".repeat(depth);
+        if hop.fragment {
+            let concrete_type = hop.field_name.as_deref().unwrap_or(&hop.type_name);
+            body.push_str(&format!("{}... on {} {{\n", indent, concrete_type));
+        } else if let Some(field_name) = &hop.field_name {
+            let call = field_call(&hop.type_name, field_name, type_map, &mut variables);
+            body.push_str(&format!("{}{} {{\n", indent, call));
+            if hop.collapsed {
+                // The graph collapsed `field -> edges -> node` into this one hop; expand it back
+                // into real selections so the emitted document matches the actual schema shape.
+                body.push_str(&format!("{}  edges {{\n", indent));
+                body.push_str(&format!("{}    node {{\n", indent));
+                depth += 2;
+            }
+        }
+        depth += 1;
+    }
+
+    body.push_str(&format!("{}{}\n", "  ".repeat(depth), result_selection(result, type_map, &mut variables)));
+
+    for d in (0..depth).rev() {
+        body.push_str(&format!("{}}}\n", "  ".repeat(d)));
+    }
+
+    let mut doc = String::new();
+    doc.push_str(root_keyword);
+    if !variables.is_empty() {
+        doc.push_str(&format!("({})", variables.join(", ")));
+    }
+    doc.push_str(" {\n");
+    doc.push_str(&body);
+    doc
+}
+
 fn main() {
     let args = Args::parse();
     let content = fs::read_to_string(&args.file).expect("Invalid file");
-    let root = Root::from_json(&content).expect("Invalid schema");
+    let is_sdl = args.sdl || matches!(args.file.rsplit('.').next(), Some("graphql" | "gql" | "sdl"));
+    let root = if is_sdl {
+        Root::from_sdl(&content).expect("Invalid schema")
+    } else {
+        Root::from_json(&content).expect("Invalid schema")
+    };
     if root.data.is_none() {
         println!("Empty schema");
         return;
     }
 
     let data = root.data.unwrap();
+    let roots = data.schema.root_type_names();
     let type_map = data.schema.get_type_map();
+    let graph = TypeGraph::build(&type_map, args.show_relay);
+    let mut dot = DotGraph::default();
+    let opts = SearchOptions {
+        containing: args.containing,
+        show_relay: args.show_relay,
+        emit_query: args.emit_query,
+        format: args.format,
+    };
+
     if args.type_only {
-        run_search_for_type(&args.search, args.containing, args.show_relay, &type_map);
+        run_search_for_type(&args.search, opts, &roots, &graph, &type_map, &mut dot);
     } else if args.field_only {
-        run_search_for_field(&args.search, args.containing, args.show_relay, &type_map);
+        run_search_for_field(&args.search, opts, &roots, &graph, &type_map, &mut dot);
     } else {
-        run_search_for_type(&args.search, args.containing, args.show_relay, &type_map);
-        run_search_for_field(&args.search, args.containing, args.show_relay, &type_map);
+        run_search_for_type(&args.search, opts, &roots, &graph, &type_map, &mut dot);
+        run_search_for_field(&args.search, opts, &roots, &graph, &type_map, &mut dot);
+    }
+
+    if !args.emit_query && args.format == OutputFormat::Dot {
+        print!("{}", dot.render());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scalar_field(name: &str, type_name: &str) -> revql::schema::Field {
+        revql::schema::Field {
+            name: name.to_string(),
+            field_type: TypeRef { name: Some(type_name.to_string()), kind: String::from("SCALAR"), of_type: None },
+            args: None,
+        }
+    }
+
+    fn object_field(name: &str, type_name: &str) -> revql::schema::Field {
+        revql::schema::Field {
+            name: name.to_string(),
+            field_type: TypeRef { name: Some(type_name.to_string()), kind: String::from("OBJECT"), of_type: None },
+            args: None,
+        }
+    }
+
+    /// A Query -> WidgetConnection -> WidgetEdge -> Widget schema whose connection field has
+    /// neither the `first`/`after` nor `first`/`offset` argument pair, so the collapsed edge
+    /// built by `TypeGraph::build` carries `pagination: None`.
+    fn connection_without_pagination_type_map() -> HashMap<String, Type> {
+        let widget = Type { name: String::from("Widget"), kind: String::from("OBJECT"), fields: Some(vec![scalar_field("id", "ID")]), possible_types: None };
+        let widget_edge = Type {
+            name: String::from("WidgetEdge"),
+            kind: String::from("OBJECT"),
+            fields: Some(vec![scalar_field("cursor", "String"), object_field("node", "Widget")]),
+            possible_types: None,
+        };
+        let widget_connection = Type {
+            name: String::from("WidgetConnection"),
+            kind: String::from("OBJECT"),
+            fields: Some(vec![object_field("edges", "WidgetEdge"), object_field("pageInfo", "PageInfo")]),
+            possible_types: None,
+        };
+        let page_info = Type {
+            name: String::from("PageInfo"),
+            kind: String::from("OBJECT"),
+            fields: Some(vec![scalar_field("hasNextPage", "Boolean"), scalar_field("hasPreviousPage", "Boolean")]),
+            possible_types: None,
+        };
+        let query = Type { name: String::from("Query"), kind: String::from("OBJECT"), fields: Some(vec![object_field("widgets", "WidgetConnection")]), possible_types: None };
+
+        [widget, widget_edge, widget_connection, page_info, query].into_iter().map(|t| (t.name.clone(), t)).collect()
+    }
+
+    fn assert_brace_balanced(doc: &str) {
+        assert_eq!(doc.matches('{').count(), doc.matches('}').count(), "emitted query must be brace-balanced:\n{doc}");
+    }
+
+    #[test]
+    fn collapsed_chain_without_recognized_pagination_still_expands_in_emitted_query() {
+        let owned = connection_without_pagination_type_map();
+        let type_map: HashMap<String, &Type> = owned.iter().map(|(name, t)| (name.clone(), t)).collect();
+        let graph = TypeGraph::build(&type_map, false);
+
+        // Drive the real CLI path - run_search strips the root edge before calling
+        // graph::search, so a target collapsed onto the very first edge hits the
+        // start == end branch, not a multi-hop BFS walk.
+        let end_type = TypeField::new("Widget", None);
+        let mut paths = run_search("Query", &end_type, &graph);
+        assert_eq!(paths.len(), 1);
+        let path = paths.remove(0);
+        assert_eq!(path.len(), 1, "field -> edges -> node should collapse into a single hop");
+        assert!(path[0].collapsed);
+        assert_eq!(path[0].pagination, None, "widgets() declares no recognized pagination args");
+
+        let doc = emit_query_doc(&end_type, &path, "query", &type_map);
+        assert!(doc.contains("edges {"), "collapsed hop must still expand to edges in the query:\n{doc}");
+        assert!(doc.contains("node {"), "collapsed hop must still expand to node in the query:\n{doc}");
+        assert_brace_balanced(&doc);
+    }
+
+    #[test]
+    fn single_hop_emit_query_is_brace_balanced() {
+        let widget = Type { name: String::from("Widget"), kind: String::from("OBJECT"), fields: Some(vec![scalar_field("id", "ID")]), possible_types: None };
+        let query = Type { name: String::from("Query"), kind: String::from("OBJECT"), fields: Some(vec![object_field("me", "Widget")]), possible_types: None };
+        let owned: HashMap<String, Type> = [widget, query].into_iter().map(|t| (t.name.clone(), t)).collect();
+        let type_map: HashMap<String, &Type> = owned.iter().map(|(name, t)| (name.clone(), t)).collect();
+        let graph = TypeGraph::build(&type_map, false);
+
+        // Widget is reached on the very first edge, so run_search must not pad the path
+        // with a field-less placeholder hop that has no matching opening brace.
+        let end_type = TypeField::new("Widget", None);
+        let mut paths = run_search("Query", &end_type, &graph);
+        assert_eq!(paths.len(), 1);
+        let path = paths.remove(0);
+        assert_eq!(path.len(), 1);
+
+        let doc = emit_query_doc(&end_type, &path, "query", &type_map);
+        assert_brace_balanced(&doc);
+    }
+
+    #[test]
+    fn path_to_json_shapes_target_and_hops() {
+        let widget = Type { name: String::from("Widget"), kind: String::from("OBJECT"), fields: Some(vec![scalar_field("id", "ID")]), possible_types: None };
+        let query = Type { name: String::from("Query"), kind: String::from("OBJECT"), fields: Some(vec![object_field("me", "Widget")]), possible_types: None };
+        let owned: HashMap<String, Type> = [widget, query].into_iter().map(|t| (t.name.clone(), t)).collect();
+        let type_map: HashMap<String, &Type> = owned.iter().map(|(name, t)| (name.clone(), t)).collect();
+
+        let result = TypeField::new("Widget", None);
+        let path = vec![TypeField::new_field("Query", String::from("me"), false, None)];
+        let doc = path_to_json(&result, &path, false, &type_map);
+
+        assert_eq!(doc, serde_json::json!({
+            "target": "Widget",
+            "path": [{ "type": "Query", "field": "me", "pagination": null }],
+        }));
+    }
+
+    #[test]
+    fn dot_graph_renders_one_edge_per_distinct_hop_pair() {
+        let widget = Type { name: String::from("Widget"), kind: String::from("OBJECT"), fields: Some(vec![scalar_field("id", "ID")]), possible_types: None };
+        let gadget = Type { name: String::from("Gadget"), kind: String::from("OBJECT"), fields: Some(vec![scalar_field("id", "ID")]), possible_types: None };
+        let query = Type {
+            name: String::from("Query"),
+            kind: String::from("OBJECT"),
+            fields: Some(vec![object_field("me", "Widget"), object_field("you", "Gadget")]),
+            possible_types: None,
+        };
+        let owned: HashMap<String, Type> = [widget, gadget, query].into_iter().map(|t| (t.name.clone(), t)).collect();
+        let type_map: HashMap<String, &Type> = owned.iter().map(|(name, t)| (name.clone(), t)).collect();
+
+        let mut dot = DotGraph::default();
+        dot.add_path(&TypeField::new("Widget", None), &[TypeField::new_field("Query", String::from("me"), false, None)], false, &type_map);
+        dot.add_path(&TypeField::new("Gadget", None), &[TypeField::new_field("Query", String::from("you"), false, None)], false, &type_map);
+
+        let rendered = dot.render();
+        assert!(rendered.starts_with("digraph reachability {\n"));
+        assert!(rendered.contains("\"Query.me\" -> \"Widget\";\n"));
+        assert!(rendered.contains("\"Query.you\" -> \"Gadget\";\n"));
+        assert_eq!(rendered.matches(" -> ").count(), 2, "two paths with no hop in common must not be deduped into one edge");
+    }
+
+    #[test]
+    fn required_args_annotation_flags_non_null_fields() {
+        let widget = Type { name: String::from("Widget"), kind: String::from("OBJECT"), fields: Some(vec![scalar_field("id", "ID")]), possible_types: None };
+        let query = Type {
+            name: String::from("Query"),
+            kind: String::from("OBJECT"),
+            fields: Some(vec![revql::schema::Field {
+                name: String::from("widget"),
+                field_type: TypeRef { name: Some(String::from("Widget")), kind: String::from("OBJECT"), of_type: None },
+                args: Some(vec![revql::schema::InputValue {
+                    name: String::from("id"),
+                    input_type: TypeRef { name: None, kind: String::from("NON_NULL"), of_type: Some(Box::new(TypeRef { name: Some(String::from("ID")), kind: String::from("SCALAR"), of_type: None })) },
+                    default_value: None,
+                }]),
+            }]),
+            possible_types: None,
+        };
+        let owned: HashMap<String, Type> = [widget, query].into_iter().map(|t| (t.name.clone(), t)).collect();
+        let type_map: HashMap<String, &Type> = owned.iter().map(|(name, t)| (name.clone(), t)).collect();
+
+        let hop = TypeField::new_field("Query", String::from("widget"), false, None);
+        assert_eq!(required_args_annotation(&hop, &type_map), "(id!)");
     }
 }
@@ -16,6 +16,17 @@ pub struct Data {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Schema {
     pub types: Vec<Type>,
+    #[serde(rename = "queryType")]
+    pub query_type: Option<RootTypeRef>,
+    #[serde(rename = "mutationType")]
+    pub mutation_type: Option<RootTypeRef>,
+    #[serde(rename = "subscriptionType")]
+    pub subscription_type: Option<RootTypeRef>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RootTypeRef {
+    pub name: String,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -23,6 +34,8 @@ pub struct Type {
     pub name: String,
     pub kind: String,
     pub fields: Option<Vec<Field>>,
+    #[serde(rename = "possibleTypes")]
+    pub possible_types: Option<Vec<TypeRef>>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -38,6 +51,16 @@ pub struct Field {
     pub name: String,
     #[serde(rename = "type")]
     pub field_type: TypeRef,
+    pub args: Option<Vec<InputValue>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InputValue {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub input_type: TypeRef,
+    #[serde(rename = "defaultValue")]
+    pub default_value: Option<String>,
 }
 
 impl Root {
@@ -46,6 +69,13 @@ impl Root {
     pub fn from_json(json: &str) -> Result<Self> {
         Ok(serde_json::from_str::<Root>(json)?)
     }
+
+    /// Returns the root node from a GraphQL SDL document, wrapping the parsed `Schema` in the
+    /// same `Root`/`Data` shape `from_json` produces so callers don't need to branch on it.
+    pub fn from_sdl(sdl: &str) -> Result<Self> {
+        let schema = crate::sdl::parse(sdl)?;
+        Ok(Root { data: Some(Data { schema }) })
+    }
 }
 
 impl Schema {
@@ -60,6 +90,16 @@ impl Schema {
     pub fn filter_type_map(&self, kind: &str) -> HashMap<String, &Type> {
         self.types.iter().filter(|t| t.name == kind).map(|t| (t.name.clone(), t)).collect()
     }
+
+    /// Returns the root operation types (query, mutation, subscription) that are actually
+    /// declared by this schema, each paired with its GraphQL operation keyword so callers don't
+    /// need to re-derive it by guessing from the type's name.
+    pub fn root_type_names(&self) -> Vec<(&'static str, &str)> {
+        [("query", &self.query_type), ("mutation", &self.mutation_type), ("subscription", &self.subscription_type)]
+            .into_iter()
+            .filter_map(|(keyword, root)| root.as_ref().map(|root| (keyword, root.name.as_str())))
+            .collect()
+    }
 }
 
 impl Type {
@@ -68,6 +108,13 @@ impl Type {
         self.kind == "OBJECT"
     }
 
+    /// Checks whether this type is an interface or union, i.e. it can be narrowed to one of its
+    /// `possibleTypes`.
+    #[inline]
+    pub fn is_abstract(&self) -> bool {
+        self.kind == "INTERFACE" || self.kind == "UNION"
+    }
+
     pub fn get_field(&self, field_name: &str, containing: bool) -> Option<&Field> {
         if let Some(fields) = &self.fields {
             if containing {
@@ -80,16 +127,6 @@ impl Type {
         }
     }
 
-    /// Returns the fields expressed as a map.
-    #[inline]
-    pub fn get_field_map(&self) -> HashMap<String, &Field> {
-        if let Some(fields) = &self.fields {
-            fields.iter().map(|f| (f.name.clone(), f)).collect()
-        } else {
-            HashMap::new()
-        }
-    }
-
     /// Checks whether this type is a relay.
     pub fn is_relay(&self) -> bool {
         if let Some(fields) = &self.fields {
@@ -124,11 +161,71 @@ impl TypeRef {
     pub fn is_object(&self) -> bool {
         self.kind == "OBJECT"
     }
+
+    /// Checks whether this type ref points at an interface or union.
+    #[inline]
+    pub fn is_abstract(&self) -> bool {
+        self.kind == "INTERFACE" || self.kind == "UNION"
+    }
 }
 
 impl Field {
-    pub fn get_type_name(&self) -> String {
-        let deep = self.field_type.get_deepest();
-        deep.name.unwrap_or_else(|| panic!("Field {} doesn't have a type - invalid schema?", self.name))
+    /// Returns the args that must be supplied to call this field: non-null and without a
+    /// default value.
+    pub fn required_args(&self) -> Vec<&InputValue> {
+        match &self.args {
+            Some(args) => args.iter().filter(|arg| arg.is_required()).collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl InputValue {
+    /// Checks whether this arg must be supplied by the caller, i.e. it's non-null and has no
+    /// default value.
+    #[inline]
+    pub fn is_required(&self) -> bool {
+        self.input_type.kind == "NON_NULL" && self.default_value.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_type_names_only_includes_declared_roots() {
+        let schema = Schema {
+            types: Vec::new(),
+            query_type: Some(RootTypeRef { name: String::from("RootQuery") }),
+            mutation_type: None,
+            subscription_type: Some(RootTypeRef { name: String::from("RootSubscription") }),
+        };
+
+        assert_eq!(schema.root_type_names(), vec![("query", "RootQuery"), ("subscription", "RootSubscription")]);
+    }
+
+    fn type_ref(name: &str, kind: &str) -> TypeRef {
+        TypeRef { name: Some(name.to_string()), kind: kind.to_string(), of_type: None }
+    }
+
+    fn non_null(inner: TypeRef) -> TypeRef {
+        TypeRef { name: None, kind: String::from("NON_NULL"), of_type: Some(Box::new(inner)) }
+    }
+
+    #[test]
+    fn required_args_excludes_nullable_and_defaulted_args() {
+        let field = Field {
+            name: String::from("widgets"),
+            field_type: type_ref("Widget", "OBJECT"),
+            args: Some(vec![
+                InputValue { name: String::from("id"), input_type: non_null(type_ref("ID", "SCALAR")), default_value: None },
+                InputValue { name: String::from("first"), input_type: type_ref("Int", "SCALAR"), default_value: None },
+                InputValue { name: String::from("after"), input_type: non_null(type_ref("String", "SCALAR")), default_value: Some(String::from("\"\"")) },
+            ]),
+        };
+
+        let required: Vec<_> = field.required_args().iter().map(|arg| arg.name.clone()).collect();
+        assert_eq!(required, vec!["id"]);
     }
 }
@@ -0,0 +1,313 @@
+//! Parses GraphQL SDL documents into this crate's introspection-shaped `Type`/`Field`/`TypeRef`
+//! model, so the BFS searcher in `graph.rs` can run against `.graphql` schema files without an
+//! introspection JSON dump.
+
+use std::collections::HashMap;
+use pest::iterators::Pair;
+use pest::Parser;
+use pest_derive::Parser as PestParser;
+use crate::error::{Error, Result};
+use crate::schema::{Field, InputValue, RootTypeRef, Schema, Type, TypeRef};
+
+#[derive(PestParser)]
+#[grammar = "sdl.pest"]
+pub struct SdlParser;
+
+/// Built-in scalars that don't need an explicit `scalar` declaration in the document.
+const BUILTIN_SCALARS: &[&str] = &["String", "Int", "Float", "Boolean", "ID"];
+
+/// Parses a GraphQL SDL document into a `Schema` using the same `Type`/`Field`/`TypeRef` model
+/// the introspection JSON front-end produces, so the BFS searcher is unchanged.
+pub fn parse(sdl: &str) -> Result<Schema> {
+    let document = SdlParser::parse(Rule::document, sdl)
+        .map_err(|e| Error::InvalidSchemaSdl(Box::new(e)))?
+        .next()
+        .ok_or(Error::InvalidSchema)?;
+
+    let mut defs: HashMap<String, RawDefOwned> = HashMap::new();
+    let mut implements: Vec<(String, String)> = Vec::new();
+    let mut root_types: HashMap<&'static str, String> = HashMap::new();
+
+    for def in document.into_inner() {
+        match def.as_rule() {
+            Rule::schema_def => {
+                for root_op in def.into_inner() {
+                    let mut parts = root_op.into_inner();
+                    let kind = parts.next().unwrap().as_str();
+                    let name = parts.next().unwrap().as_str().to_string();
+                    let kind = match kind {
+                        "query" => "query",
+                        "mutation" => "mutation",
+                        _ => "subscription",
+                    };
+                    root_types.insert(kind, name);
+                }
+            }
+            Rule::type_def => {
+                let mut parts = def.into_inner();
+                let name = parts.next().unwrap().as_str().to_string();
+                let mut fields = Vec::new();
+                for part in parts {
+                    match part.as_rule() {
+                        Rule::implements_clause => {
+                            for iface in part.into_inner() {
+                                implements.push((iface.as_str().to_string(), name.clone()));
+                            }
+                        }
+                        Rule::field_def => fields.push(owned_field(part)),
+                        _ => {}
+                    }
+                }
+                defs.insert(name.clone(), RawDefOwned { name, kind: String::from("OBJECT"), fields, possible_types: Vec::new() });
+            }
+            Rule::interface_def => {
+                let mut parts = def.into_inner();
+                let name = parts.next().unwrap().as_str().to_string();
+                let fields = parts.map(owned_field).collect();
+                defs.insert(name.clone(), RawDefOwned { name, kind: String::from("INTERFACE"), fields, possible_types: Vec::new() });
+            }
+            Rule::union_def => {
+                let mut parts = def.into_inner();
+                let name = parts.next().unwrap().as_str().to_string();
+                let members = parts.map(|m| m.as_str().to_string()).collect();
+                defs.insert(name.clone(), RawDefOwned { name, kind: String::from("UNION"), fields: Vec::new(), possible_types: members });
+            }
+            Rule::enum_def => {
+                let mut parts = def.into_inner();
+                let name = parts.next().unwrap().as_str().to_string();
+                defs.insert(name.clone(), RawDefOwned { name, kind: String::from("ENUM"), fields: Vec::new(), possible_types: Vec::new() });
+            }
+            Rule::scalar_def => {
+                let name = def.into_inner().next().unwrap().as_str().to_string();
+                defs.insert(name.clone(), RawDefOwned { name, kind: String::from("SCALAR"), fields: Vec::new(), possible_types: Vec::new() });
+            }
+            Rule::EOI => {}
+            _ => {}
+        }
+    }
+
+    for scalar in BUILTIN_SCALARS {
+        defs.entry(scalar.to_string()).or_insert_with(|| {
+            RawDefOwned { name: scalar.to_string(), kind: String::from("SCALAR"), fields: Vec::new(), possible_types: Vec::new() }
+        });
+    }
+
+    for (interface_name, object_name) in implements {
+        if let Some(iface) = defs.get_mut(&interface_name) {
+            iface.possible_types.push(object_name);
+        }
+    }
+
+    let kind_of = |name: &str| defs.get(name).map(|d| d.kind.clone()).unwrap_or_else(|| String::from("SCALAR"));
+
+    let types = defs.values().map(|def| Type {
+        name: def.name.clone(),
+        kind: def.kind.clone(),
+        fields: if def.kind == "OBJECT" || def.kind == "INTERFACE" {
+            Some(def.fields.iter().map(|f| owned_to_field(f, &kind_of)).collect())
+        } else {
+            None
+        },
+        possible_types: if def.possible_types.is_empty() {
+            None
+        } else {
+            Some(def.possible_types.iter().map(|name| TypeRef { name: Some(name.clone()), kind: kind_of(name), of_type: None }).collect())
+        },
+    }).collect();
+
+    let query_name = root_types.get("query").cloned().or_else(|| defs.contains_key("Query").then(|| String::from("Query")));
+    let mutation_name = root_types.get("mutation").cloned().or_else(|| defs.contains_key("Mutation").then(|| String::from("Mutation")));
+    let subscription_name = root_types.get("subscription").cloned().or_else(|| defs.contains_key("Subscription").then(|| String::from("Subscription")));
+
+    Ok(Schema {
+        types,
+        query_type: query_name.map(|name| RootTypeRef { name }),
+        mutation_type: mutation_name.map(|name| RootTypeRef { name }),
+        subscription_type: subscription_name.map(|name| RootTypeRef { name }),
+    })
+}
+
+/// A type/interface/union/enum/scalar definition collected from the document, before its
+/// fields' `TypeRef`s are resolved against the full set of definitions.
+struct RawDefOwned {
+    name: String,
+    kind: String,
+    fields: Vec<OwnedField>,
+    possible_types: Vec<String>,
+}
+
+struct OwnedField {
+    name: String,
+    args: Vec<OwnedArg>,
+    type_ref: OwnedTypeRef,
+}
+
+struct OwnedArg {
+    name: String,
+    type_ref: OwnedTypeRef,
+    default_value: Option<String>,
+}
+
+/// A `type_ref` parse tree flattened into an owned, recursive shape ahead of `TypeRef::kind`
+/// resolution (named leaves need the full definition map before their `OBJECT`/`INTERFACE`/...
+/// kind is known).
+enum OwnedTypeRef {
+    Named { name: String, non_null: bool },
+    List { of: Box<OwnedTypeRef>, non_null: bool },
+}
+
+fn owned_field(pair: Pair<Rule>) -> OwnedField {
+    let mut name = None;
+    let mut args = Vec::new();
+    let mut type_ref = None;
+    // Matched by rule rather than position: an optional leading description means `ident` isn't
+    // always the first child.
+    for part in pair.into_inner() {
+        match part.as_rule() {
+            Rule::ident => name = Some(part.as_str().to_string()),
+            Rule::args_def => args = part.into_inner().map(owned_arg).collect(),
+            Rule::type_ref => type_ref = Some(owned_type_ref(part)),
+            _ => {}
+        }
+    }
+    OwnedField {
+        name: name.expect("field missing name"),
+        args,
+        type_ref: type_ref.expect("field missing type"),
+    }
+}
+
+fn owned_arg(pair: Pair<Rule>) -> OwnedArg {
+    let mut name = None;
+    let mut type_ref = None;
+    let mut default_value = None;
+    for part in pair.into_inner() {
+        match part.as_rule() {
+            Rule::ident => name = Some(part.as_str().to_string()),
+            Rule::type_ref => type_ref = Some(owned_type_ref(part)),
+            Rule::default_value => default_value = Some(part.into_inner().next().unwrap().as_str().to_string()),
+            _ => {}
+        }
+    }
+    OwnedArg {
+        name: name.expect("arg missing name"),
+        type_ref: type_ref.expect("arg missing type"),
+        default_value,
+    }
+}
+
+fn owned_type_ref(pair: Pair<Rule>) -> OwnedTypeRef {
+    let inner = pair.into_inner().next().unwrap();
+    match inner.as_rule() {
+        Rule::named_type => {
+            let mut parts = inner.into_inner();
+            let name = parts.next().unwrap().as_str().to_string();
+            let non_null = parts.next().is_some();
+            OwnedTypeRef::Named { name, non_null }
+        }
+        Rule::list_type => {
+            let mut parts = inner.into_inner();
+            let of = Box::new(owned_type_ref(parts.next().unwrap()));
+            let non_null = parts.next().is_some();
+            OwnedTypeRef::List { of, non_null }
+        }
+        _ => unreachable!("type_ref can only be named_type or list_type"),
+    }
+}
+
+fn resolve_type_ref(type_ref: &OwnedTypeRef, kind_of: &impl Fn(&str) -> String) -> TypeRef {
+    match type_ref {
+        OwnedTypeRef::Named { name, non_null } => {
+            let named = TypeRef { name: Some(name.clone()), kind: kind_of(name), of_type: None };
+            wrap_non_null(named, *non_null)
+        }
+        OwnedTypeRef::List { of, non_null } => {
+            let list = TypeRef { name: None, kind: String::from("LIST"), of_type: Some(Box::new(resolve_type_ref(of, kind_of))) };
+            wrap_non_null(list, *non_null)
+        }
+    }
+}
+
+fn wrap_non_null(inner: TypeRef, non_null: bool) -> TypeRef {
+    if non_null {
+        TypeRef { name: None, kind: String::from("NON_NULL"), of_type: Some(Box::new(inner)) }
+    } else {
+        inner
+    }
+}
+
+fn owned_to_field(field: &OwnedField, kind_of: &impl Fn(&str) -> String) -> Field {
+    Field {
+        name: field.name.clone(),
+        field_type: resolve_type_ref(&field.type_ref, kind_of),
+        args: if field.args.is_empty() {
+            None
+        } else {
+            Some(field.args.iter().map(|arg| InputValue {
+                name: arg.name.clone(),
+                input_type: resolve_type_ref(&arg.type_ref, kind_of),
+                default_value: arg.default_value.clone(),
+            }).collect())
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn find<'a>(schema: &'a Schema, name: &str) -> &'a Type {
+        schema.types.iter().find(|t| t.name == name).unwrap_or_else(|| panic!("no type named {}", name))
+    }
+
+    #[test]
+    fn parses_interface_and_implementing_object() {
+        let schema = parse(r#"
+            interface Node { id: ID! }
+            type User implements Node { id: ID! }
+        "#).unwrap();
+
+        let node = find(&schema, "Node");
+        let possible = node.possible_types.as_ref().expect("Node should list implementers");
+        assert_eq!(possible.iter().map(|t| t.name.as_deref().unwrap()).collect::<Vec<_>>(), vec!["User"]);
+    }
+
+    #[test]
+    fn parses_union_members() {
+        let schema = parse("union Result = Ok | Err\ntype Ok { id: ID! }\ntype Err { id: ID! }").unwrap();
+
+        let result = find(&schema, "Result");
+        assert_eq!(result.kind, "UNION");
+        let possible = result.possible_types.as_ref().unwrap();
+        assert_eq!(possible.iter().map(|t| t.name.as_deref().unwrap()).collect::<Vec<_>>(), vec!["Ok", "Err"]);
+    }
+
+    #[test]
+    fn parses_required_and_defaulted_args() {
+        let schema = parse(r#"
+            type Query {
+                users(first: Int = 10, after: String!): String
+            }
+        "#).unwrap();
+
+        let field = find(&schema, "Query").get_field("users", false).unwrap();
+        let required: Vec<_> = field.required_args().iter().map(|a| a.name.clone()).collect();
+        assert_eq!(required, vec!["after".to_string()]);
+    }
+
+    #[test]
+    fn ignores_descriptions() {
+        let schema = parse(r#"
+            """
+            A user of the system.
+            """
+            type User {
+                "the user's id"
+                id: ID!
+            }
+        "#).unwrap();
+
+        let user = find(&schema, "User");
+        assert!(user.get_field("id", false).is_some());
+    }
+}